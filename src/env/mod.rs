@@ -2,11 +2,14 @@
 //! environment.
 
 mod native;
+mod shared;
 mod simulated;
 
 pub use self::native::NativeEnv;
+pub use self::shared::SharedSimulatedEnv;
 pub use self::simulated::SimulatedEnv;
 
+use std::borrow::Cow;
 use std::env;
 use std::ffi;
 use std::io;
@@ -61,6 +64,9 @@ pub trait Env {
     /// The iterator type returned by `vars_os()`.
     type VarsOsIter: Iterator<Item = (ffi::OsString, ffi::OsString)>;
 
+    /// The iterator type returned by `split_paths()`.
+    type SplitPathsIter: Iterator<Item = PathBuf>;
+
     /// Returns the arguments which this program was started with (normally passed via the command
     /// line).
     ///
@@ -86,17 +92,51 @@ pub trait Env {
     /// more information.
     fn current_exe(&self) -> io::Result<PathBuf>;
 
-    /// Returns the path of the current user's home directory if known.
+    /// Joins a collection of paths into an `OsString` suitable for use as the value of a
+    /// `PATH`-style environment variable, using the platform's path separator (`:` on Unix, `;` on
+    /// Windows). Returns an error if any path contains the separator character.
+    ///
+    /// See [`std::env::join_paths`](https://doc.rust-lang.org/std/env/fn.join_paths.html) for more
+    /// information.
+    fn join_paths<I, P>(&self, paths: I) -> Result<ffi::OsString, env::JoinPathsError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<ffi::OsStr>;
+
+    /// Parses `value` into an iterator over the individual paths of a `PATH`-style environment
+    /// variable, splitting on the platform's path separator (`:` on Unix, `;` on Windows).
     ///
-    /// See [`std::env::home_dir`](https://doc.rust-lang.org/std/env/fn.home_dir.html) for more
+    /// See [`std::env::split_paths`](https://doc.rust-lang.org/std/env/fn.split_paths.html) for more
     /// information.
-    #[deprecated(
-        since = "0.2.0",
-        note = "This function's behavior is unexpected and probably not what you want. \
-                Consider using the home_dir function from crates.io/crates/dirs instead."
-    )]
+    fn split_paths(&self, value: &ffi::OsStr) -> Self::SplitPathsIter;
+
+    /// Returns the path of the current user's home directory if known.
+    ///
+    /// On Unix this is resolved from the `HOME` environment variable; on Windows it is resolved
+    /// from `USERPROFILE`.
     fn home_dir(&self) -> Option<PathBuf>;
 
+    /// Returns the path of the directory in which the current user's configuration files should be
+    /// stored if known.
+    ///
+    /// On Unix this is `$XDG_CONFIG_HOME`, falling back to `$HOME/.config`; on Windows it is
+    /// `%APPDATA%`.
+    fn config_dir(&self) -> Option<PathBuf>;
+
+    /// Returns the path of the directory in which the current user's data files should be stored if
+    /// known.
+    ///
+    /// On Unix this is `$XDG_DATA_HOME`, falling back to `$HOME/.local/share`; on Windows it is
+    /// `%APPDATA%`.
+    fn data_dir(&self) -> Option<PathBuf>;
+
+    /// Returns the path of the directory in which the current user's cache files should be stored if
+    /// known.
+    ///
+    /// On Unix this is `$XDG_CACHE_HOME`, falling back to `$HOME/.cache`; on Windows it is
+    /// `%LOCALAPPDATA%`.
+    fn cache_dir(&self) -> Option<PathBuf>;
+
     /// Removes an environment variable from the environment of the currently running process.
     ///
     /// See [`std::env::remove_var`](https://doc.rust-lang.org/std/env/fn.remove_var.html) for more
@@ -143,4 +183,22 @@ pub trait Env {
     ///
     /// See [`std::env::vars_os`](https://doc.rust-lang.org/std/env/fn.vars_os.html) for more information.
     fn vars_os(&self) -> Self::VarsOsIter;
+
+    /// Returns the architecture of the target this code was built for.
+    ///
+    /// See [`std::env::consts::ARCH`](https://doc.rust-lang.org/std/env/consts/constant.ARCH.html)
+    /// for more information.
+    fn arch(&self) -> Cow<'static, str>;
+
+    /// Returns the operating system of the target this code was built for.
+    ///
+    /// See [`std::env::consts::OS`](https://doc.rust-lang.org/std/env/consts/constant.OS.html) for
+    /// more information.
+    fn os(&self) -> Cow<'static, str>;
+
+    /// Returns the family of operating systems the target this code was built for belongs to.
+    ///
+    /// See [`std::env::consts::FAMILY`](https://doc.rust-lang.org/std/env/consts/constant.FAMILY.html)
+    /// for more information.
+    fn family(&self) -> Cow<'static, str>;
 }
@@ -0,0 +1,128 @@
+use std::borrow::Cow;
+use std::env;
+use std::ffi;
+use std::path::{Path, PathBuf};
+
+use super::Env;
+use utils;
+
+/// Provides inspection and manipulation of the real, process-global environment.
+///
+/// This is a thin wrapper around [`std::env`](https://doc.rust-lang.org/std/env/); most methods
+/// delegate directly to the corresponding free function or constant. The user-directory methods
+/// (`home_dir`, `config_dir`, `data_dir` and `cache_dir`) instead resolve their paths from the
+/// platform's environment variables.
+pub struct NativeEnv;
+
+impl Env for NativeEnv {
+    type ArgsIter = env::Args;
+    type ArgsOsIter = env::ArgsOs;
+    type VarsIter = env::Vars;
+    type VarsOsIter = env::VarsOs;
+    type SplitPathsIter = ::std::vec::IntoIter<PathBuf>;
+
+    fn args(&self) -> Self::ArgsIter {
+        env::args()
+    }
+
+    fn args_os(&self) -> Self::ArgsOsIter {
+        env::args_os()
+    }
+
+    fn current_dir(&self) -> ::std::io::Result<PathBuf> {
+        env::current_dir()
+    }
+
+    fn current_exe(&self) -> ::std::io::Result<PathBuf> {
+        env::current_exe()
+    }
+
+    fn join_paths<I, P>(&self, paths: I) -> Result<ffi::OsString, env::JoinPathsError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<ffi::OsStr>,
+    {
+        env::join_paths(paths)
+    }
+
+    fn split_paths(&self, value: &ffi::OsStr) -> Self::SplitPathsIter {
+        env::split_paths(value).collect::<Vec<_>>().into_iter()
+    }
+
+    fn home_dir(&self) -> Option<PathBuf> {
+        let key = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+        utils::non_empty(env::var_os(key)).map(PathBuf::from)
+    }
+
+    fn config_dir(&self) -> Option<PathBuf> {
+        if cfg!(windows) {
+            utils::non_empty(env::var_os("APPDATA")).map(PathBuf::from)
+        } else {
+            utils::resolve_dir(&["XDG_CONFIG_HOME"], self.home_dir().as_ref(), &[".config"])
+        }
+    }
+
+    fn data_dir(&self) -> Option<PathBuf> {
+        if cfg!(windows) {
+            utils::non_empty(env::var_os("APPDATA")).map(PathBuf::from)
+        } else {
+            utils::resolve_dir(
+                &["XDG_DATA_HOME"],
+                self.home_dir().as_ref(),
+                &[".local", "share"],
+            )
+        }
+    }
+
+    fn cache_dir(&self) -> Option<PathBuf> {
+        if cfg!(windows) {
+            utils::non_empty(env::var_os("LOCALAPPDATA")).map(PathBuf::from)
+        } else {
+            utils::resolve_dir(&["XDG_CACHE_HOME"], self.home_dir().as_ref(), &[".cache"])
+        }
+    }
+
+    fn remove_var<K: AsRef<ffi::OsStr>>(&mut self, k: K) {
+        env::remove_var(k)
+    }
+
+    fn set_current_dir<P: AsRef<Path>>(&mut self, path: P) -> ::std::io::Result<()> {
+        env::set_current_dir(path)
+    }
+
+    fn set_var<K: AsRef<ffi::OsStr>, V: AsRef<ffi::OsStr>>(&mut self, k: K, v: V) {
+        env::set_var(k, v)
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        env::temp_dir()
+    }
+
+    fn var<K: AsRef<ffi::OsStr>>(&self, key: K) -> Result<String, env::VarError> {
+        env::var(key)
+    }
+
+    fn var_os<K: AsRef<ffi::OsStr>>(&self, key: K) -> Option<ffi::OsString> {
+        env::var_os(key)
+    }
+
+    fn vars(&self) -> Self::VarsIter {
+        env::vars()
+    }
+
+    fn vars_os(&self) -> Self::VarsOsIter {
+        env::vars_os()
+    }
+
+    fn arch(&self) -> Cow<'static, str> {
+        Cow::Borrowed(env::consts::ARCH)
+    }
+
+    fn os(&self) -> Cow<'static, str> {
+        Cow::Borrowed(env::consts::OS)
+    }
+
+    fn family(&self) -> Cow<'static, str> {
+        Cow::Borrowed(env::consts::FAMILY)
+    }
+}
@@ -0,0 +1,157 @@
+use std::borrow::Cow;
+use std::env;
+use std::ffi;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use super::{Env, SimulatedEnv};
+
+/// A cloneable, thread-shareable handle to a [`SimulatedEnv`](struct.SimulatedEnv.html).
+///
+/// The real process environment is a single piece of global mutable state shared by every thread;
+/// `SimulatedEnv` deliberately scopes that state to a single instance, which makes it awkward to
+/// use from tools (such as rustup) that run many tests in-process across threads. Every clone of a
+/// `SharedSimulatedEnv` points at the same underlying environment, so a `set_var`, `remove_var` or
+/// `set_current_dir` performed through one clone is visible through all of the others, mirroring
+/// true process-global semantics.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use io_providers::{Env, SharedSimulatedEnv};
+///
+/// let env = SharedSimulatedEnv::new();
+/// let other = env.clone();
+///
+/// let handle = thread::spawn(move || {
+///     let mut other = other;
+///     other.set_var("FOO", "bar");
+/// });
+/// handle.join().unwrap();
+///
+/// assert_eq!(env.var("FOO").unwrap(), "bar");
+/// ```
+#[derive(Clone, Default)]
+pub struct SharedSimulatedEnv {
+    inner: Arc<RwLock<SimulatedEnv>>,
+}
+
+impl SharedSimulatedEnv {
+    /// Creates a new `SharedSimulatedEnv` wrapping a fresh `SimulatedEnv`.
+    pub fn new() -> SharedSimulatedEnv {
+        SharedSimulatedEnv {
+            inner: Arc::new(RwLock::new(SimulatedEnv::new())),
+        }
+    }
+}
+
+impl From<SimulatedEnv> for SharedSimulatedEnv {
+    fn from(env: SimulatedEnv) -> SharedSimulatedEnv {
+        SharedSimulatedEnv {
+            inner: Arc::new(RwLock::new(env)),
+        }
+    }
+}
+
+impl Env for SharedSimulatedEnv {
+    type ArgsIter = ::std::vec::IntoIter<String>;
+    type ArgsOsIter = ::std::vec::IntoIter<ffi::OsString>;
+    type VarsIter = ::std::vec::IntoIter<(String, String)>;
+    type VarsOsIter = ::std::vec::IntoIter<(ffi::OsString, ffi::OsString)>;
+    type SplitPathsIter = ::std::vec::IntoIter<PathBuf>;
+
+    fn args(&self) -> Self::ArgsIter {
+        self.inner.read().unwrap().args().collect::<Vec<_>>().into_iter()
+    }
+
+    fn args_os(&self) -> Self::ArgsOsIter {
+        self.inner.read().unwrap().args_os().collect::<Vec<_>>().into_iter()
+    }
+
+    fn current_dir(&self) -> io::Result<PathBuf> {
+        self.inner.read().unwrap().current_dir()
+    }
+
+    fn current_exe(&self) -> io::Result<PathBuf> {
+        self.inner.read().unwrap().current_exe()
+    }
+
+    fn join_paths<I, P>(&self, paths: I) -> Result<ffi::OsString, env::JoinPathsError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<ffi::OsStr>,
+    {
+        self.inner.read().unwrap().join_paths(paths)
+    }
+
+    fn split_paths(&self, value: &ffi::OsStr) -> Self::SplitPathsIter {
+        self.inner
+            .read()
+            .unwrap()
+            .split_paths(value)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn home_dir(&self) -> Option<PathBuf> {
+        self.inner.read().unwrap().home_dir()
+    }
+
+    fn config_dir(&self) -> Option<PathBuf> {
+        self.inner.read().unwrap().config_dir()
+    }
+
+    fn data_dir(&self) -> Option<PathBuf> {
+        self.inner.read().unwrap().data_dir()
+    }
+
+    fn cache_dir(&self) -> Option<PathBuf> {
+        self.inner.read().unwrap().cache_dir()
+    }
+
+    fn remove_var<K: AsRef<ffi::OsStr>>(&mut self, k: K) {
+        self.inner.write().unwrap().remove_var(k);
+    }
+
+    fn set_current_dir<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.inner.write().unwrap().set_current_dir(path)
+    }
+
+    fn set_var<K: AsRef<ffi::OsStr>, V: AsRef<ffi::OsStr>>(&mut self, k: K, v: V) {
+        self.inner.write().unwrap().set_var(k, v);
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        self.inner.read().unwrap().temp_dir()
+    }
+
+    fn var<K: AsRef<ffi::OsStr>>(&self, key: K) -> Result<String, env::VarError> {
+        self.inner.read().unwrap().var(key)
+    }
+
+    fn var_os<K: AsRef<ffi::OsStr>>(&self, key: K) -> Option<ffi::OsString> {
+        self.inner.read().unwrap().var_os(key)
+    }
+
+    fn vars(&self) -> Self::VarsIter {
+        self.inner.read().unwrap().vars().collect::<Vec<_>>().into_iter()
+    }
+
+    fn vars_os(&self) -> Self::VarsOsIter {
+        self.inner.read().unwrap().vars_os().collect::<Vec<_>>().into_iter()
+    }
+
+    fn arch(&self) -> Cow<'static, str> {
+        self.inner.read().unwrap().arch()
+    }
+
+    fn os(&self) -> Cow<'static, str> {
+        self.inner.read().unwrap().os()
+    }
+
+    fn family(&self) -> Cow<'static, str> {
+        self.inner.read().unwrap().family()
+    }
+}
@@ -0,0 +1,279 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::env;
+use std::ffi;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::Env;
+
+/// Provides a simulated environment which can be inspected and manipulated independently of the
+/// real, process-global environment.
+///
+/// Every piece of state exposed by [`Env`](trait.Env.html) can be configured directly through a
+/// matching setter, which makes it possible to exercise environment-dependent code against known
+/// inputs without touching the real OS environment.
+pub struct SimulatedEnv {
+    args: Vec<String>,
+    current_dir: PathBuf,
+    current_exe: PathBuf,
+    vars: HashMap<ffi::OsString, ffi::OsString>,
+    home_dir: Option<PathBuf>,
+    config_dir: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    temp_dir: PathBuf,
+    arch: String,
+    os: String,
+    family: String,
+}
+
+impl SimulatedEnv {
+    /// Creates a new `SimulatedEnv` with empty arguments and variables, a current working directory
+    /// of `/`, and target constants matching the host the code was compiled for.
+    pub fn new() -> SimulatedEnv {
+        SimulatedEnv {
+            args: Vec::new(),
+            current_dir: PathBuf::from("/"),
+            current_exe: PathBuf::from("/"),
+            vars: HashMap::new(),
+            home_dir: None,
+            config_dir: None,
+            data_dir: None,
+            cache_dir: None,
+            temp_dir: PathBuf::from("/tmp"),
+            arch: env::consts::ARCH.to_owned(),
+            os: env::consts::OS.to_owned(),
+            family: env::consts::FAMILY.to_owned(),
+        }
+    }
+
+    /// Sets the arguments returned by `args()`/`args_os()`.
+    pub fn set_args(&mut self, args: Vec<String>) {
+        self.args = args;
+    }
+
+    /// Sets the path returned by `current_exe()`.
+    pub fn set_current_exe<P: AsRef<Path>>(&mut self, path: P) {
+        self.current_exe = path.as_ref().to_path_buf();
+    }
+
+    /// Sets the path returned by `home_dir()`.
+    pub fn set_home_dir<P: AsRef<Path>>(&mut self, path: P) {
+        self.home_dir = Some(path.as_ref().to_path_buf());
+    }
+
+    /// Sets the path returned by `config_dir()`.
+    pub fn set_config_dir<P: AsRef<Path>>(&mut self, path: P) {
+        self.config_dir = Some(path.as_ref().to_path_buf());
+    }
+
+    /// Sets the path returned by `data_dir()`.
+    pub fn set_data_dir<P: AsRef<Path>>(&mut self, path: P) {
+        self.data_dir = Some(path.as_ref().to_path_buf());
+    }
+
+    /// Sets the path returned by `cache_dir()`.
+    pub fn set_cache_dir<P: AsRef<Path>>(&mut self, path: P) {
+        self.cache_dir = Some(path.as_ref().to_path_buf());
+    }
+
+    /// Sets the path returned by `temp_dir()`.
+    pub fn set_temp_dir<P: AsRef<Path>>(&mut self, path: P) {
+        self.temp_dir = path.as_ref().to_path_buf();
+    }
+
+    /// Sets the value returned by `arch()`, letting a test pretend to run on a different target
+    /// architecture.
+    pub fn set_arch<S: Into<String>>(&mut self, arch: S) {
+        self.arch = arch.into();
+    }
+
+    /// Sets the value returned by `os()`, letting a test pretend to run on a different operating
+    /// system.
+    pub fn set_os<S: Into<String>>(&mut self, os: S) {
+        self.os = os.into();
+    }
+
+    /// Sets the value returned by `family()`, letting a test pretend to run on a different operating
+    /// system family.
+    pub fn set_family<S: Into<String>>(&mut self, family: S) {
+        self.family = family.into();
+    }
+}
+
+impl Default for SimulatedEnv {
+    fn default() -> SimulatedEnv {
+        SimulatedEnv::new()
+    }
+}
+
+impl Env for SimulatedEnv {
+    type ArgsIter = ::std::vec::IntoIter<String>;
+    type ArgsOsIter = ::std::vec::IntoIter<ffi::OsString>;
+    type VarsIter = ::std::vec::IntoIter<(String, String)>;
+    type VarsOsIter = ::std::vec::IntoIter<(ffi::OsString, ffi::OsString)>;
+    type SplitPathsIter = ::std::vec::IntoIter<PathBuf>;
+
+    fn args(&self) -> Self::ArgsIter {
+        self.args.clone().into_iter()
+    }
+
+    fn args_os(&self) -> Self::ArgsOsIter {
+        self.args
+            .iter()
+            .map(ffi::OsString::from)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn current_dir(&self) -> io::Result<PathBuf> {
+        Ok(self.current_dir.clone())
+    }
+
+    fn current_exe(&self) -> io::Result<PathBuf> {
+        Ok(self.current_exe.clone())
+    }
+
+    fn join_paths<I, P>(&self, paths: I) -> Result<ffi::OsString, env::JoinPathsError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<ffi::OsStr>,
+    {
+        env::join_paths(paths)
+    }
+
+    fn split_paths(&self, value: &ffi::OsStr) -> Self::SplitPathsIter {
+        env::split_paths(value).collect::<Vec<_>>().into_iter()
+    }
+
+    fn home_dir(&self) -> Option<PathBuf> {
+        self.home_dir.clone()
+    }
+
+    fn config_dir(&self) -> Option<PathBuf> {
+        self.config_dir.clone()
+    }
+
+    fn data_dir(&self) -> Option<PathBuf> {
+        self.data_dir.clone()
+    }
+
+    fn cache_dir(&self) -> Option<PathBuf> {
+        self.cache_dir.clone()
+    }
+
+    fn remove_var<K: AsRef<ffi::OsStr>>(&mut self, k: K) {
+        self.vars.remove(k.as_ref());
+    }
+
+    fn set_current_dir<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.current_dir = path.as_ref().to_path_buf();
+        Ok(())
+    }
+
+    fn set_var<K: AsRef<ffi::OsStr>, V: AsRef<ffi::OsStr>>(&mut self, k: K, v: V) {
+        self.vars
+            .insert(k.as_ref().to_owned(), v.as_ref().to_owned());
+    }
+
+    fn temp_dir(&self) -> PathBuf {
+        self.temp_dir.clone()
+    }
+
+    fn var<K: AsRef<ffi::OsStr>>(&self, key: K) -> Result<String, env::VarError> {
+        match self.vars.get(key.as_ref()) {
+            None => Err(env::VarError::NotPresent),
+            Some(value) => value
+                .clone()
+                .into_string()
+                .map_err(env::VarError::NotUnicode),
+        }
+    }
+
+    fn var_os<K: AsRef<ffi::OsStr>>(&self, key: K) -> Option<ffi::OsString> {
+        self.vars.get(key.as_ref()).cloned()
+    }
+
+    fn vars(&self) -> Self::VarsIter {
+        self.vars
+            .iter()
+            .filter_map(|(k, v)| match (k.to_str(), v.to_str()) {
+                (Some(k), Some(v)) => Some((k.to_owned(), v.to_owned())),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn vars_os(&self) -> Self::VarsOsIter {
+        self.vars
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn arch(&self) -> Cow<'static, str> {
+        Cow::Owned(self.arch.clone())
+    }
+
+    fn os(&self) -> Cow<'static, str> {
+        Cow::Owned(self.os.clone())
+    }
+
+    fn family(&self) -> Cow<'static, str> {
+        Cow::Owned(self.family.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use env::Env;
+
+    #[test]
+    fn var_roundtrips_through_setter() {
+        let mut env = SimulatedEnv::new();
+        env.set_var("FOO", "bar");
+        assert_eq!(env.var("FOO").unwrap(), "bar");
+        env.remove_var("FOO");
+        assert!(env.var("FOO").is_err());
+    }
+
+    #[test]
+    fn split_paths_uses_platform_separator() {
+        let env = SimulatedEnv::new();
+        let sep = if cfg!(windows) { "a;b" } else { "a:b" };
+        let parts: Vec<_> = env.split_paths(sep.as_ref()).collect();
+        assert_eq!(parts, vec![PathBuf::from("a"), PathBuf::from("b")]);
+    }
+
+    #[test]
+    fn join_paths_rejects_separator() {
+        let env = SimulatedEnv::new();
+        let bad = if cfg!(windows) { "a;b" } else { "a:b" };
+        assert!(env.join_paths(vec![bad]).is_err());
+    }
+
+    #[test]
+    fn user_directories_are_settable() {
+        let mut env = SimulatedEnv::new();
+        assert!(env.config_dir().is_none());
+        env.set_home_dir("/home/me");
+        env.set_config_dir("/home/me/.config");
+        assert_eq!(env.home_dir().unwrap(), PathBuf::from("/home/me"));
+        assert_eq!(env.config_dir().unwrap(), PathBuf::from("/home/me/.config"));
+    }
+
+    #[test]
+    fn target_constants_are_settable() {
+        let mut env = SimulatedEnv::new();
+        env.set_os("windows");
+        env.set_arch("x86_64");
+        env.set_family("windows");
+        assert_eq!(env.os(), "windows");
+        assert_eq!(env.arch(), "x86_64");
+        assert_eq!(env.family(), "windows");
+    }
+}
@@ -1,16 +1,20 @@
+pub mod env;
 pub mod stream;
 mod utils;
 
 use std::io;
 
+pub use env::{Env, NativeEnv, SharedSimulatedEnv, SimulatedEnv};
+pub use stream::{NativeStreamProvider, SimulatedStreamProvider};
+
 /// Provides access to input, output and error streams.
 pub trait StreamProvider {
     /// Gets the input stream.
-    fn input(&mut self) -> &mut io::Read;
+    fn input(&mut self) -> &mut dyn io::Read;
 
     /// Gets the output stream.
-    fn output(&mut self) -> &mut io::Write;
+    fn output(&mut self) -> &mut dyn io::Write;
 
     /// Gets the error stream.
-    fn error(&mut self) -> &mut io::Write;
-}
\ No newline at end of file
+    fn error(&mut self) -> &mut dyn io::Write;
+}
@@ -0,0 +1,7 @@
+//! Defines implementations of the [`StreamProvider`](../trait.StreamProvider.html) trait.
+
+mod native;
+mod simulated;
+
+pub use self::native::NativeStreamProvider;
+pub use self::simulated::SimulatedStreamProvider;
@@ -0,0 +1,41 @@
+use std::io;
+
+use StreamProvider;
+
+/// Provides access to the real standard input, output and error streams of the process.
+pub struct NativeStreamProvider {
+    input: io::Stdin,
+    output: io::Stdout,
+    error: io::Stderr,
+}
+
+impl NativeStreamProvider {
+    /// Creates a new `NativeStreamProvider` backed by the process's standard streams.
+    pub fn new() -> NativeStreamProvider {
+        NativeStreamProvider {
+            input: io::stdin(),
+            output: io::stdout(),
+            error: io::stderr(),
+        }
+    }
+}
+
+impl Default for NativeStreamProvider {
+    fn default() -> NativeStreamProvider {
+        NativeStreamProvider::new()
+    }
+}
+
+impl StreamProvider for NativeStreamProvider {
+    fn input(&mut self) -> &mut dyn io::Read {
+        &mut self.input
+    }
+
+    fn output(&mut self) -> &mut dyn io::Write {
+        &mut self.output
+    }
+
+    fn error(&mut self) -> &mut dyn io::Write {
+        &mut self.error
+    }
+}
@@ -0,0 +1,110 @@
+use std::borrow::Cow;
+use std::io;
+
+use StreamProvider;
+
+/// Provides simulated input, output and error streams backed by in-memory buffers.
+///
+/// Output written to the output and error streams is accumulated rather than discarded, so that a
+/// test can read back exactly what the code under test produced. This makes stream mocking
+/// symmetric with how [`SimulatedEnv`](../env/struct.SimulatedEnv.html) is inspected.
+pub struct SimulatedStreamProvider {
+    input: io::Cursor<Vec<u8>>,
+    output: Vec<u8>,
+    error: Vec<u8>,
+}
+
+impl SimulatedStreamProvider {
+    /// Creates a new `SimulatedStreamProvider` with an empty input stream.
+    pub fn new() -> SimulatedStreamProvider {
+        SimulatedStreamProvider::with_input(Vec::new())
+    }
+
+    /// Creates a new `SimulatedStreamProvider` whose input stream yields `input`.
+    pub fn with_input<I: Into<Vec<u8>>>(input: I) -> SimulatedStreamProvider {
+        SimulatedStreamProvider {
+            input: io::Cursor::new(input.into()),
+            output: Vec::new(),
+            error: Vec::new(),
+        }
+    }
+
+    /// Returns the bytes written so far to the output stream.
+    pub fn written_output(&self) -> &[u8] {
+        &self.output
+    }
+
+    /// Returns the bytes written so far to the error stream.
+    pub fn written_error(&self) -> &[u8] {
+        &self.error
+    }
+
+    /// Returns the bytes written so far to the output stream, decoded as UTF-8 (lossily, replacing
+    /// any invalid sequences).
+    pub fn written_output_str(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.output)
+    }
+
+    /// Returns the bytes written so far to the error stream, decoded as UTF-8 (lossily, replacing
+    /// any invalid sequences).
+    pub fn written_error_str(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.error)
+    }
+
+    /// Clears the accumulated output and error buffers, for example between phases of a test.
+    pub fn reset(&mut self) {
+        self.output.clear();
+        self.error.clear();
+    }
+}
+
+impl Default for SimulatedStreamProvider {
+    fn default() -> SimulatedStreamProvider {
+        SimulatedStreamProvider::new()
+    }
+}
+
+impl StreamProvider for SimulatedStreamProvider {
+    fn input(&mut self) -> &mut dyn io::Read {
+        &mut self.input
+    }
+
+    fn output(&mut self) -> &mut dyn io::Write {
+        &mut self.output
+    }
+
+    fn error(&mut self) -> &mut dyn io::Write {
+        &mut self.error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use StreamProvider;
+
+    #[test]
+    fn captures_output_and_error() {
+        let mut streams = SimulatedStreamProvider::new();
+        write!(streams.output(), "hello").unwrap();
+        write!(streams.error(), "oops").unwrap();
+
+        assert_eq!(streams.written_output(), b"hello");
+        assert_eq!(streams.written_error(), b"oops");
+        assert_eq!(streams.written_output_str(), "hello");
+        assert_eq!(streams.written_error_str(), "oops");
+    }
+
+    #[test]
+    fn reset_clears_buffers() {
+        let mut streams = SimulatedStreamProvider::new();
+        write!(streams.output(), "first").unwrap();
+        write!(streams.error(), "oops").unwrap();
+        streams.reset();
+        assert!(streams.written_output().is_empty());
+        assert!(streams.written_error().is_empty());
+
+        write!(streams.output(), "second").unwrap();
+        assert_eq!(streams.written_output_str(), "second");
+    }
+}
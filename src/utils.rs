@@ -0,0 +1,33 @@
+//! Internal helpers shared across the provider implementations.
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// Treats an environment-variable value as absent when it is unset or set to the empty string, so
+/// that the user-directory lookups (e.g. the XDG variables) fall back to their default in both
+/// cases.
+pub fn non_empty(value: Option<OsString>) -> Option<OsString> {
+    value.filter(|v| !v.is_empty())
+}
+
+/// Resolves a user directory from the first non-empty environment variable named in `keys`,
+/// falling back to `home`-relative `default` segments when none of them are set.
+///
+/// Following the XDG conventions, a variable holding a relative path is ignored rather than used
+/// as-is.
+pub fn resolve_dir(keys: &[&str], home: Option<&PathBuf>, default: &[&str]) -> Option<PathBuf> {
+    for key in keys {
+        if let Some(value) = non_empty(::std::env::var_os(key)) {
+            let path = PathBuf::from(value);
+            if path.is_absolute() {
+                return Some(path);
+            }
+        }
+    }
+
+    home.map(|home| {
+        let mut path = home.clone();
+        path.extend(default);
+        path
+    })
+}